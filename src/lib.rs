@@ -92,27 +92,43 @@ pub mod cmds;
 pub mod flags;
 mod node;
 pub mod params;
+mod sections;
 
 use std::{
-    env::{self, ArgsOs},
+    env,
     ffi::{OsStr, OsString},
     fmt,
     iter::Peekable,
+    vec,
 };
 
 use yansi::Paint;
+pub use yansi::Style;
 
 use self::node::{Cmd, Help, Node as Seal};
+pub use self::sections::{Heading, HelpSections};
 
 #[doc(hidden)]
 pub enum Branch {
-    Skip(Peekable<ArgsOs>),
-    Help(Peekable<ArgsOs>),
+    Skip(Args),
+    Help(Args),
     Done,
 }
 
+#[doc(hidden)]
+pub struct Context {
+    sections: HelpSections,
+    on_subcommand: Vec<PathFn>,
+    path: Vec<&'static str>,
+    print: fn(&str),
+}
+
+type Args = Peekable<vec::IntoIter<OsString>>;
+
 type CmdFn = fn(&dyn Opts);
 
+type PathFn = fn(&[&'static str]);
+
 /// A sealed trait implemented on the generic of [`Clot`].
 pub trait Opts: Seal {
     fn flag(&self, _c: char) -> bool {
@@ -134,6 +150,8 @@ impl<T: Seal> Opts for T {}
 pub struct Clot<T: Opts = Help> {
     opts: T,
     cmd_fn: Option<CmdFn>,
+    sections: Option<HelpSections>,
+    on_subcommand: Option<PathFn>,
 }
 
 impl Clot {
@@ -144,6 +162,8 @@ impl Clot {
         Self {
             opts: Help::new(help),
             cmd_fn: None,
+            sections: None,
+            on_subcommand: None,
         }
     }
 }
@@ -156,6 +176,24 @@ impl<T: Opts> Clot<T> {
         self
     }
 
+    /// Override the section headings of the help text.
+    ///
+    /// Subcommands inherit the headings unless they set their own.
+    pub fn help_heading_style(mut self, sections: HelpSections) -> Self {
+        self.sections = Some(sections);
+        self
+    }
+
+    /// Add a callback receiving the command path of every matched subcommand,
+    /// run after any callbacks set by ancestors.
+    ///
+    /// Called after the parent's `--help` check, before the subcommand's help
+    /// text or [`run`](Self::run) callback.
+    pub fn on_subcommand(mut self, f: PathFn) -> Self {
+        self.on_subcommand = Some(f);
+        self
+    }
+
     /// Create a new subcommand.
     ///
     /// # Panics
@@ -178,6 +216,8 @@ impl<T: Opts> Clot<T> {
         Clot {
             opts: Cmd::new(self.opts, name, f),
             cmd_fn: self.cmd_fn,
+            sections: self.sections,
+            on_subcommand: self.on_subcommand,
         }
     }
 
@@ -202,26 +242,57 @@ impl<T: Opts> Clot<T> {
 
     /// Validate the arguments and execute the selected subcommands.
     pub fn execute(self) {
-        let mut iter = env::args_os().peekable();
+        self.execute_args(env::args_os(), |text| print!("{text}"));
+    }
+
+    /// Execution with arguments starting with the command name
+    fn execute_args(
+        self,
+        args: impl IntoIterator<Item = OsString>,
+        print: fn(&str),
+    ) {
+        let mut iter = args.into_iter().collect::<Vec<_>>().into_iter();
         let name = iter.next().expect("Failed to get command name");
 
-        self.execute_with(name, iter);
+        self.execute_with(
+            name,
+            iter.peekable(),
+            &mut Context {
+                sections: HelpSections::new(),
+                on_subcommand: Vec::new(),
+                path: Vec::new(),
+                print,
+            },
+        );
     }
 
     /// Execution of a specific subcommand
-    fn execute_with(self, name: OsString, mut args: Peekable<ArgsOs>) {
+    fn execute_with(self, name: OsString, mut args: Args, ctx: &mut Context) {
         let has_fields = self.opts.has_fields();
 
+        if let Some(sections) = self.sections {
+            ctx.sections = sections;
+        }
+
+        if let Some(on_subcommand) = self.on_subcommand {
+            ctx.on_subcommand.push(on_subcommand);
+        }
+
         // If no arguments are provided to subcommand without command fn,
         // then display help
         if args.peek().is_none() && self.cmd_fn.is_none() {
-            node::help(&self.opts, &name, has_fields);
+            node::help(&self.opts, &name, has_fields, ctx);
         }
 
         while let Some(arg) = args.next() {
             // If passed `--help` or `help` when no fields, then display help.
-            if node::maybe_help(&self.opts, &arg, &name, args.peek().is_some())
-            {
+            if node::maybe_help(
+                &self.opts,
+                &arg,
+                &name,
+                args.peek().is_some(),
+                ctx,
+            ) {
                 if let Some(arg) = args.next() {
                     unexpected(name, arg);
                 }
@@ -229,7 +300,7 @@ impl<T: Opts> Clot<T> {
                 return;
             }
 
-            args = match self.opts.branch(&arg, has_fields, &name, args) {
+            args = match self.opts.branch(&arg, has_fields, &name, args, ctx) {
                 Branch::Skip(args) => args,
                 Branch::Help(_args) => {
                     unexpected(name, arg);
@@ -264,3 +335,70 @@ fn unexpected(name: OsString, arg: OsString) {
         format_args!("{} --help", OsDisplay(&name)).bright().blue(),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    static PATHS: Mutex<Vec<(&str, Vec<&str>)>> = Mutex::new(Vec::new());
+    static HELP: Mutex<String> = Mutex::new(String::new());
+
+    const SECTIONS: HelpSections = HelpSections::new()
+        .usage(Heading::new("USAGE").suffix(""))
+        .commands(Heading::new("COMMANDS").suffix(" -"));
+
+    fn record_root(path: &[&'static str]) {
+        PATHS.lock().unwrap().push(("root", path.to_vec()));
+    }
+
+    fn record_a(path: &[&'static str]) {
+        PATHS.lock().unwrap().push(("a", path.to_vec()));
+    }
+
+    fn capture(text: &str) {
+        HELP.lock().unwrap().push_str(text);
+    }
+
+    fn a() -> Clot<impl Opts> {
+        Clot::new("A").on_subcommand(record_a).cmd("b", b)
+    }
+
+    fn b() -> Clot<impl Opts> {
+        Clot::new("B").run(|_| {})
+    }
+
+    #[test]
+    fn on_subcommand_nested_path() {
+        Clot::new("Root")
+            .on_subcommand(record_root)
+            .cmd("a", a)
+            .execute_args(["root", "a", "b"].map(OsString::from), |_| {});
+
+        assert_eq!(
+            *PATHS.lock().unwrap(),
+            [
+                ("root", vec!["a"]),
+                ("root", vec!["a", "b"]),
+                ("a", vec!["a", "b"]),
+            ],
+        );
+    }
+
+    #[test]
+    fn help_heading_style_inherited() {
+        Clot::new("Root")
+            .help_heading_style(SECTIONS)
+            .cmd("a", a)
+            .execute_args(["root", "a", "--help"].map(OsString::from), capture);
+
+        let help = HELP.lock().unwrap();
+
+        assert!(help.starts_with("A\n\n"));
+        assert!(help.contains(&format!("\n{}\n", SECTIONS.usage)));
+        assert!(help.contains(&format!("\n{}\n", SECTIONS.commands)));
+        assert!(!help.contains("Usage"));
+        assert!(!help.contains("Commands"));
+    }
+}
@@ -1,8 +1,8 @@
-use std::{cell::Cell, env::ArgsOs, ffi::OsStr, iter::Peekable};
+use std::{cell::Cell, ffi::OsStr, fmt};
 
 use yansi::Paint;
 
-use crate::{Branch, Clot, Opts, OsDisplay};
+use crate::{Args, Branch, Clot, Context, HelpSections, Opts, OsDisplay};
 
 pub trait Node {
     /// Return true if this node or any previous node contains fields.
@@ -14,17 +14,26 @@ pub trait Node {
     /// Return true if this node or any previous node contains parameters.
     fn has_params(&self) -> bool;
 
-    /// Print help for commands on this node and all previous nodes.
-    fn help_cmds(&self, has_fields: bool);
+    /// Write help for commands on this node and all previous nodes.
+    fn help_cmds(
+        &self,
+        has_fields: bool,
+        f: &mut dyn fmt::Write,
+    ) -> fmt::Result;
 
-    /// Print help for fields on this node and all previous nodes.
-    fn help_fields(&self, name: &OsStr);
+    /// Write help for fields on this node and all previous nodes.
+    fn help_fields(&self, name: &OsStr, f: &mut dyn fmt::Write) -> fmt::Result;
 
-    /// Print help for flags on this node and all previous nodes.
-    fn help_flags(&self, has_fields: bool, name: &OsStr);
+    /// Write help for flags on this node and all previous nodes.
+    fn help_flags(
+        &self,
+        has_fields: bool,
+        name: &OsStr,
+        f: &mut dyn fmt::Write,
+    ) -> fmt::Result;
 
-    /// Print help for parameters on this node and all previous nodes.
-    fn help_params(&self, name: &OsStr);
+    /// Write help for parameters on this node and all previous nodes.
+    fn help_params(&self, name: &OsStr, f: &mut dyn fmt::Write) -> fmt::Result;
 
     /// Get help text for this command
     fn get_help_text(&self) -> &'static str;
@@ -34,7 +43,8 @@ pub trait Node {
         what: &OsStr,
         has_fields: bool,
         name: &OsStr,
-        args: Peekable<ArgsOs>,
+        args: Args,
+        ctx: &mut Context,
     ) -> Branch;
 }
 
@@ -59,26 +69,51 @@ impl Node for Help {
         false
     }
 
-    fn help_fields(&self, _name: &OsStr) {}
+    fn help_fields(
+        &self,
+        _name: &OsStr,
+        _f: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        Ok(())
+    }
 
-    fn help_cmds(&self, has_fields: bool) {
+    fn help_cmds(
+        &self,
+        has_fields: bool,
+        f: &mut dyn fmt::Write,
+    ) -> fmt::Result {
         if has_fields {
-            println!(
+            writeln!(
+                f,
                 "   {}\n      Display this help message",
                 "--help".cyan().bright(),
-            );
+            )
         } else {
-            println!(
+            writeln!(
+                f,
                 "   {}, {}\n      Display this help message",
                 "help".cyan().bright(),
                 "--help".cyan().bright(),
-            );
+            )
         }
     }
 
-    fn help_flags(&self, _has_fields: bool, _name: &OsStr) {}
+    fn help_flags(
+        &self,
+        _has_fields: bool,
+        _name: &OsStr,
+        _f: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        Ok(())
+    }
 
-    fn help_params(&self, _name: &OsStr) {}
+    fn help_params(
+        &self,
+        _name: &OsStr,
+        _f: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        Ok(())
+    }
 
     fn get_help_text(&self) -> &'static str {
         self.0
@@ -89,7 +124,8 @@ impl Node for Help {
         _what: &OsStr,
         _has_fields: bool,
         _name: &OsStr,
-        args: Peekable<ArgsOs>,
+        args: Args,
+        _ctx: &mut Context,
     ) -> Branch {
         Branch::Help(args)
     }
@@ -122,31 +158,41 @@ impl<T: Opts, U: Node, F: FnOnce() -> Clot<U>> Node for Cmd<T, U, F> {
         self.prev.has_params()
     }
 
-    fn help_fields(&self, name: &OsStr) {
-        self.prev.help_fields(name)
+    fn help_fields(&self, name: &OsStr, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.prev.help_fields(name, f)
     }
 
-    fn help_cmds(&self, has_fields: bool) {
+    fn help_cmds(
+        &self,
+        has_fields: bool,
+        f: &mut dyn fmt::Write,
+    ) -> fmt::Result {
         let help = (self.f.take().unwrap())().opts.get_help_text();
 
-        self.prev.help_cmds(has_fields);
+        self.prev.help_cmds(has_fields, f)?;
 
         if has_fields {
-            println!(
+            writeln!(
+                f,
                 "   {}\n      {help}",
                 format_args!("--{}", self.name).cyan().bright(),
-            );
+            )
         } else {
-            println!("   {}\n      {help}", self.name.cyan().bright());
+            writeln!(f, "   {}\n      {help}", self.name.cyan().bright())
         }
     }
 
-    fn help_flags(&self, has_fields: bool, name: &OsStr) {
-        self.prev.help_flags(has_fields, name)
+    fn help_flags(
+        &self,
+        has_fields: bool,
+        name: &OsStr,
+        f: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        self.prev.help_flags(has_fields, name, f)
     }
 
-    fn help_params(&self, name: &OsStr) {
-        self.prev.help_params(name)
+    fn help_params(&self, name: &OsStr, f: &mut dyn fmt::Write) -> fmt::Result {
+        self.prev.help_params(name, f)
     }
 
     fn get_help_text(&self) -> &'static str {
@@ -158,9 +204,10 @@ impl<T: Opts, U: Node, F: FnOnce() -> Clot<U>> Node for Cmd<T, U, F> {
         what: &OsStr,
         has_fields: bool,
         name: &OsStr,
-        args: Peekable<ArgsOs>,
+        args: Args,
+        ctx: &mut Context,
     ) -> Branch {
-        let args = match self.prev.branch(what, has_fields, name, args) {
+        let args = match self.prev.branch(what, has_fields, name, args, ctx) {
             Branch::Skip(args) | Branch::Help(args) => args,
             Branch::Done => return Branch::Done,
         };
@@ -176,8 +223,17 @@ impl<T: Opts, U: Node, F: FnOnce() -> Clot<U>> Node for Cmd<T, U, F> {
         };
 
         if what == Some(self.name) {
-            (self.f.take().unwrap())()
-                .execute_with(what.unwrap().to_string().into(), args);
+            ctx.path.push(self.name);
+
+            for on_subcommand in &ctx.on_subcommand {
+                (on_subcommand)(&ctx.path)
+            }
+
+            (self.f.take().unwrap())().execute_with(
+                what.unwrap().to_string().into(),
+                args,
+                ctx,
+            );
             Branch::Done
         } else {
             Branch::Help(args)
@@ -185,41 +241,61 @@ impl<T: Opts, U: Node, F: FnOnce() -> Clot<U>> Node for Cmd<T, U, F> {
     }
 }
 
-pub(super) fn help(node: &impl Node, name: &OsStr, has_fields: bool) {
+pub(super) fn help(
+    node: &impl Node,
+    name: &OsStr,
+    has_fields: bool,
+    ctx: &Context,
+) {
+    let mut text = String::new();
+
+    render_help(node, name, has_fields, &ctx.sections, &mut text)
+        .expect("Failed to render help");
+    (ctx.print)(&text);
+}
+
+fn render_help(
+    node: &impl Node,
+    name: &OsStr,
+    has_fields: bool,
+    sections: &HelpSections,
+    f: &mut dyn fmt::Write,
+) -> fmt::Result {
     let help_text = node.get_help_text();
     let options = if has_fields {
         format!(
             "{} {}\n",
-            format_args!("{}", OsDisplay(&name)).bright().blue(),
+            format_args!("{}", OsDisplay(name)).bright().blue(),
             "[OPTIONS] [FIELDS] [OPTIONS]".bright().cyan(),
         )
     } else {
         String::new()
     };
 
-    println!(
-        "{help_text}\n\n{}:\n{}   {} {}\n",
-        "Usage".bold().bright().white(),
+    writeln!(
+        f,
+        "{help_text}\n\n{}\n{}   {} {}\n",
+        sections.usage,
         options,
-        format_args!("{}", OsDisplay(&name)).bright().blue(),
+        format_args!("{}", OsDisplay(name)).bright().blue(),
         "[COMMAND] ...".bright().cyan(),
-    );
+    )?;
 
     if has_fields {
-        node.help_fields(name);
+        node.help_fields(name, f)?;
     }
 
     if node.has_flags() {
-        node.help_flags(has_fields, name);
+        node.help_flags(has_fields, name, f)?;
     }
 
     if node.has_params() {
-        node.help_params(name);
+        node.help_params(name, f)?;
     }
 
-    println!("{}", "Commands:".bold().bright().white());
-    node.help_cmds(has_fields);
-    println!();
+    writeln!(f, "{}", sections.commands)?;
+    node.help_cmds(has_fields, f)?;
+    writeln!(f)
 }
 
 pub(super) fn maybe_help(
@@ -227,6 +303,7 @@ pub(super) fn maybe_help(
     what: &OsStr,
     name: &OsStr,
     dont_print: bool,
+    ctx: &Context,
 ) -> bool {
     let has_fields = node.has_fields();
 
@@ -235,7 +312,7 @@ pub(super) fn maybe_help(
     }
 
     if !dont_print {
-        help(node, name, has_fields);
+        help(node, name, has_fields, ctx);
     }
 
     true
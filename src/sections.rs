@@ -0,0 +1,112 @@
+use std::fmt;
+
+use yansi::{Paint, Style};
+
+/// A single section heading in the help text
+#[derive(Debug, Copy, Clone)]
+pub struct Heading {
+    label: &'static str,
+    suffix: &'static str,
+    style: Style,
+}
+
+impl Heading {
+    /// Create a new section heading.
+    ///
+    ///  - `label` text of the heading, without any suffix
+    pub const fn new(label: &'static str) -> Self {
+        Self {
+            label,
+            suffix: ":",
+            style: Style::new().bold().bright().white(),
+        }
+    }
+
+    /// Set the unstyled text printed directly after the label (default `:`).
+    pub const fn suffix(mut self, suffix: &'static str) -> Self {
+        self.suffix = suffix;
+        self
+    }
+
+    /// Set the style of the label (default bold bright white).
+    pub const fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl fmt::Display for Heading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.label.paint(self.style), self.suffix)
+    }
+}
+
+/// Per-section heading overrides for the help text
+#[derive(Debug, Copy, Clone)]
+pub struct HelpSections {
+    pub(super) usage: Heading,
+    pub(super) commands: Heading,
+}
+
+impl HelpSections {
+    /// Create the default set of section headings.
+    pub const fn new() -> Self {
+        Self {
+            usage: Heading::new("Usage"),
+            // Styled colon, as it's always been printed
+            commands: Heading::new("Commands:").suffix(""),
+        }
+    }
+
+    /// Override the `Usage` heading.
+    pub const fn usage(mut self, heading: Heading) -> Self {
+        self.usage = heading;
+        self
+    }
+
+    /// Override the `Commands` heading.
+    pub const fn commands(mut self, heading: Heading) -> Self {
+        self.commands = heading;
+        self
+    }
+}
+
+impl Default for HelpSections {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_headings() {
+        let sections = HelpSections::new()
+            .usage(Heading::new("USAGE").suffix(""))
+            .commands(
+                Heading::new("COMMANDS").suffix(" -").style(Style::new()),
+            );
+
+        assert_eq!(
+            sections.usage.to_string(),
+            "USAGE".bold().bright().white().to_string(),
+        );
+        assert_eq!(sections.commands.to_string(), "COMMANDS -");
+    }
+
+    #[test]
+    fn default_headings() {
+        let sections = HelpSections::default();
+
+        assert_eq!(
+            sections.usage.to_string(),
+            format!("{}:", "Usage".bold().bright().white()),
+        );
+        assert_eq!(
+            sections.commands.to_string(),
+            "Commands:".bold().bright().white().to_string(),
+        );
+    }
+}